@@ -0,0 +1,112 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT license.
+// SPDX-License-Identifier: MIT
+
+use rand::Rng;
+use serde::Deserialize;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tonic::Status;
+
+/// The strategy a `LoadBalancer` uses to pick among its endpoints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LoadBalancerStrategy {
+    /// Pick a uniformly random endpoint on every call.
+    #[default]
+    Random,
+    /// Cycle through endpoints in order, wrapping back to the start.
+    RoundRobin,
+}
+
+impl FromStr for LoadBalancerStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().replace(['-', '_'], "").as_str() {
+            "random" => Ok(Self::Random),
+            "roundrobin" => Ok(Self::RoundRobin),
+            other => Err(format!("'{other}' is not a known load balancer strategy")),
+        }
+    }
+}
+
+/// Chooses among multiple candidate endpoints returned by service discovery, so a single
+/// unreachable instance does not take the whole service down with it.
+pub struct LoadBalancer {
+    endpoints: Vec<String>,
+    strategy: LoadBalancerStrategy,
+    next_index: AtomicUsize,
+}
+
+impl LoadBalancer {
+    /// Create a new `LoadBalancer` over `endpoints`.
+    ///
+    /// # Arguments
+    /// * `strategy` - The strategy used to pick among `endpoints`.
+    /// * `endpoints` - The candidate endpoint URIs.
+    pub fn new(strategy: LoadBalancerStrategy, endpoints: Vec<String>) -> Result<Self, Status> {
+        if endpoints.is_empty() {
+            return Err(Status::not_found(
+                "Cannot build a LoadBalancer over an empty endpoint list",
+            ));
+        }
+
+        Ok(Self {
+            endpoints,
+            strategy,
+            next_index: AtomicUsize::new(0),
+        })
+    }
+
+    /// Pick the next endpoint according to this `LoadBalancer`'s strategy.
+    ///
+    /// Calling this again after a failed attempt against the returned endpoint advances to a
+    /// different one (deterministically for `RoundRobin`, probabilistically for `Random`),
+    /// which is how this is meant to be combined with [`crate::retry::retry_async`].
+    pub fn next(&self) -> &str {
+        let index = match self.strategy {
+            LoadBalancerStrategy::Random => rand::thread_rng().gen_range(0..self.endpoints.len()),
+            LoadBalancerStrategy::RoundRobin => {
+                self.next_index.fetch_add(1, Ordering::Relaxed) % self.endpoints.len()
+            }
+        };
+
+        &self.endpoints[index]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_robin_wraps_around() {
+        let lb = LoadBalancer::new(
+            LoadBalancerStrategy::RoundRobin,
+            vec!["a".to_string(), "b".to_string(), "c".to_string()],
+        )
+        .unwrap();
+
+        let picks: Vec<&str> = (0..6).map(|_| lb.next()).collect();
+
+        assert_eq!(picks, vec!["a", "b", "c", "a", "b", "c"]);
+    }
+
+    #[test]
+    fn random_always_picks_a_known_endpoint() {
+        let endpoints = vec!["a".to_string(), "b".to_string()];
+        let lb = LoadBalancer::new(LoadBalancerStrategy::Random, endpoints.clone()).unwrap();
+
+        for _ in 0..50 {
+            assert!(endpoints.iter().any(|endpoint| endpoint == lb.next()));
+        }
+    }
+
+    #[test]
+    fn new_rejects_an_empty_endpoint_list() {
+        let result = LoadBalancer::new(LoadBalancerStrategy::Random, Vec::new());
+
+        assert!(result.is_err());
+    }
+}
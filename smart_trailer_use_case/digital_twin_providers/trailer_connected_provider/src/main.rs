@@ -5,26 +5,38 @@
 use digital_twin_model::trailer_v1;
 
 use env_logger::{Builder, Target};
-use interfaces::chariott::service_discovery::core::v1::service_registry_client::ServiceRegistryClient;
-use interfaces::chariott::service_discovery::core::v1::DiscoverRequest;
 use interfaces::invehicle_digital_twin::v1::invehicle_digital_twin_client::InvehicleDigitalTwinClient;
 use interfaces::invehicle_digital_twin::v1::{EndpointInfo, EntityAccessInfo, RegisterRequest};
-use log::{debug, info, LevelFilter};
+use load_balancer::LoadBalancer;
+use log::{debug, info, warn, LevelFilter};
+use retry::retry_async;
+use service_discovery::chariott::ChariottServiceDiscovery;
+use service_discovery::consul::ConsulServiceDiscovery;
+use service_discovery::zeroconf::ZeroconfServiceDiscovery;
+use service_discovery::ServiceDiscovery;
+use settings::{DiscoveryBackendKind, Settings};
 use smart_trailer_interfaces::trailer_connected_provider::v1::trailer_connected_provider_server::TrailerConnectedProviderServer;
 use std::net::SocketAddr;
+use std::str::FromStr;
+use std::time::Duration;
+use tls::TlsConfig;
 use tokio::signal;
 use tonic::transport::Server;
-use tonic::{Request, Status};
+use tonic::{Code, Status};
 use trailer_connected_provider_impl::TrailerConnectedProviderImpl;
 
+mod load_balancer;
+mod retry;
+mod service_discovery;
+mod settings;
+mod tls;
 mod trailer_connected_provider_impl;
 
 const GRPC_PROTOCOL: &str = "grpc";
 const OPERATION_GET: &str = "Get";
 
-// TODO: These could be added in configuration
-const SERVICE_DISCOVERY_URI: &str = "http://0.0.0.0:50000";
-const PROVIDER_AUTHORITY: &str = "0.0.0.0:55000";
+/// How long the zeroconf backend browses the network for a matching responder.
+const ZEROCONF_BROWSE_TIMEOUT: Duration = Duration::from_secs(5);
 
 pub const INVEHICLE_DIGITAL_TWIN_SERVICE_NAMESPACE: &str = "sdv.ibeji";
 pub const INVEHICLE_DIGITAL_TWIN_SERVICE_NAME: &str = "invehicle_digital_twin";
@@ -32,59 +44,21 @@ pub const INVEHICLE_DIGITAL_TWIN_SERVICE_VERSION: &str = "1.0";
 pub const INVEHICLE_DIGITAL_TWIN_SERVICE_COMMUNICATION_KIND: &str = "grpc+proto";
 pub const INVEHICLE_DIGITAL_TWIN_SERVICE_COMMUNICATION_REFERENCE: &str = "https://github.com/eclipse-ibeji/ibeji/blob/main/interfaces/digital_twin/v1/digital_twin.proto";
 
-/// Use Chariott Service Discovery to discover a service.
-///
-/// # Arguments
-/// * `chariott_uri` - Chariott's URI.
-/// * `namespace` - The service's namespace.
-/// * `name` - The service's name.
-/// * `version` - The service's version.
-/// # `communication_kind` - The service's communication kind.
-/// # `communication_reference` - The service's communication reference.
-pub async fn discover_service_using_chariott(
-    chariott_uri: &str,
-    namespace: &str,
-    name: &str,
-    version: &str,
-    communication_kind: &str,
-    communication_reference: &str,
-) -> Result<String, Status> {
-    let mut client = ServiceRegistryClient::connect(chariott_uri.to_string())
-        .await
-        .map_err(|e| Status::internal(e.to_string()))?;
-
-    let request = Request::new(DiscoverRequest {
-        namespace: namespace.to_string(),
-        name: name.to_string(),
-        version: version.to_string(),
-    });
-
-    let response = client
-        .discover(request)
-        .await
-        .map_err(|error| Status::internal(error.to_string()))?;
-
-    let service = response.into_inner().service.ok_or_else(|| Status::not_found("Did not find a service in Chariott with namespace '{namespace}', name '{name}' and version {version}"))?;
-
-    if service.communication_kind != communication_kind
-        && service.communication_reference != communication_reference
-    {
-        return Err(Status::not_found(
-            "Did not find a service in Chariott with namespace '{namespace}', name '{name}' and version {version} that has communication kind '{communication_kind} and communication_reference '{communication_reference}''",
-        ));
-    }
-
-    Ok(service.uri)
-}
+pub const PROVIDER_SERVICE_NAMESPACE: &str = "sdv.ibeji";
+pub const PROVIDER_SERVICE_NAME: &str = "trailer_connected_provider";
+pub const PROVIDER_SERVICE_VERSION: &str = "1.0";
+pub const PROVIDER_SERVICE_COMMUNICATION_REFERENCE: &str = "https://github.com/eclipse-ibeji/ibeji/blob/main/smart_trailer_use_case/interfaces/trailer_connected_provider/v1/trailer_connected_provider.proto";
 
 /// Register the "is trailer connected" property's endpoint.
 ///
 /// # Arguments
 /// * `invehicle_digital_twin_uri` - The In-Vehicle Digital Twin URI.
 /// * `provider_uri` - The provider's URI.
+/// * `tls` - TLS configuration used to connect to the In-Vehicle Digital Twin.
 async fn register_entity(
     invehicle_digital_twin_uri: &str,
     provider_uri: &str,
+    tls: &TlsConfig,
 ) -> Result<(), Status> {
     let is_trailer_connected_endpoint_info = EndpointInfo {
         protocol: GRPC_PROTOCOL.to_string(),
@@ -99,9 +73,8 @@ async fn register_entity(
         endpoint_info_list: vec![is_trailer_connected_endpoint_info],
     };
 
-    let mut client = InvehicleDigitalTwinClient::connect(invehicle_digital_twin_uri.to_string())
-        .await
-        .map_err(|e| Status::internal(e.to_string()))?;
+    let channel = tls.connect(invehicle_digital_twin_uri).await?;
+    let mut client = InvehicleDigitalTwinClient::new(channel);
     let request = tonic::Request::new(RegisterRequest {
         entity_access_info_list: vec![entity_access_info],
     });
@@ -112,41 +85,140 @@ async fn register_entity(
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let settings = Settings::load()?;
+
     // Set up logging.
     Builder::new()
-        .filter(None, LevelFilter::Debug)
+        .filter(
+            None,
+            LevelFilter::from_str(&settings.log_level).unwrap_or(LevelFilter::Debug),
+        )
         .target(Target::Stdout)
         .init();
 
     info!("The Provider has started.");
 
-    let provider_uri = format!("http://{PROVIDER_AUTHORITY}");
+    let tls: TlsConfig = settings.tls.clone().into();
+
+    let provider_uri = settings
+        .advertised_uri
+        .clone()
+        .unwrap_or_else(|| tls.upgrade_uri(&format!("http://{}", settings.provider_authority)));
     debug!("The Provider URI is {}", &provider_uri);
 
     // Setup the HTTP server.
-    let addr: SocketAddr = PROVIDER_AUTHORITY.parse()?;
+    let addr: SocketAddr = settings.provider_authority.parse()?;
     let provider_impl = TrailerConnectedProviderImpl::default();
-    let server_future = Server::builder()
+    let mut server_builder = Server::builder();
+    if tls.enabled {
+        server_builder = server_builder.tls_config(tls.server_tls_config()?)?;
+    }
+    let server_future = server_builder
         .add_service(TrailerConnectedProviderServer::new(provider_impl))
         .serve(addr);
-    info!("The HTTP server is listening on address '{PROVIDER_AUTHORITY}'");
+    info!(
+        "The HTTP server is listening on address '{}'",
+        settings.provider_authority
+    );
+
+    // Advertise this provider via mDNS/zeroconf so it can be found on the local network
+    // segment without Chariott running. This is best-effort: a host with no multicast support
+    // (e.g. a container or CI network) should not prevent the provider from starting, especially
+    // when the selected discovery backend doesn't rely on mDNS at all.
+    let _mdns_daemon = match service_discovery::zeroconf::advertise_provider(
+        &settings.provider_authority,
+        PROVIDER_SERVICE_NAMESPACE,
+        PROVIDER_SERVICE_NAME,
+        PROVIDER_SERVICE_VERSION,
+        GRPC_PROTOCOL,
+        trailer_v1::trailer::is_trailer_connected::ID,
+    ) {
+        Ok(daemon) => Some(daemon),
+        Err(e) => {
+            warn!("Failed to advertise the Provider via mDNS/zeroconf, continuing without it: {e}");
+            None
+        }
+    };
 
     // Get the In-vehicle Digital Twin Uri from the service discovery system
-    // This could be enhances to add retries for robustness
-    let invehicle_digital_twin_uri = discover_service_using_chariott(
-        SERVICE_DISCOVERY_URI,
-        INVEHICLE_DIGITAL_TWIN_SERVICE_NAMESPACE,
-        INVEHICLE_DIGITAL_TWIN_SERVICE_NAME,
-        INVEHICLE_DIGITAL_TWIN_SERVICE_VERSION,
-        INVEHICLE_DIGITAL_TWIN_SERVICE_COMMUNICATION_KIND,
-        INVEHICLE_DIGITAL_TWIN_SERVICE_COMMUNICATION_REFERENCE,
+    let discovery_backend: Box<dyn ServiceDiscovery> = match settings.discovery_backend {
+        DiscoveryBackendKind::Chariott => Box::new(ChariottServiceDiscovery::new(
+            settings.discovery_uri.clone(),
+            tls.clone(),
+        )),
+        DiscoveryBackendKind::Consul => {
+            Box::new(ConsulServiceDiscovery::new(settings.discovery_uri.clone()))
+        }
+        DiscoveryBackendKind::Zeroconf => {
+            Box::new(ZeroconfServiceDiscovery::new(ZEROCONF_BROWSE_TIMEOUT))
+        }
+    };
+
+    // Register this provider with the selected discovery backend so other services can find it.
+    // Backends that handle advertisement another way (zeroconf, via the mDNS daemon above) or
+    // that don't support registration at all return `Status::unimplemented`, which is not an
+    // error here.
+    match retry_async(
+        settings.retry.max_attempts,
+        settings.retry.initial_delay(),
+        settings.retry.max_delay(),
+        || {
+            discovery_backend.register(
+                PROVIDER_SERVICE_NAMESPACE,
+                PROVIDER_SERVICE_NAME,
+                PROVIDER_SERVICE_VERSION,
+                GRPC_PROTOCOL,
+                PROVIDER_SERVICE_COMMUNICATION_REFERENCE,
+                &provider_uri,
+            )
+        },
     )
-    .await?;
+    .await
+    {
+        Ok(()) => info!("Registered the Provider with the discovery backend."),
+        Err(status) if status.code() == Code::Unimplemented => {
+            debug!("The discovery backend does not support registration.");
+        }
+        Err(status) => return Err(status.into()),
+    }
 
-    debug!("Sending a register request to the In-Vehicle Digital Twin Service URI {invehicle_digital_twin_uri}");
+    let invehicle_digital_twin_uris = retry_async(
+        settings.retry.max_attempts,
+        settings.retry.initial_delay(),
+        settings.retry.max_delay(),
+        || {
+            discovery_backend.discover(
+                INVEHICLE_DIGITAL_TWIN_SERVICE_NAMESPACE,
+                INVEHICLE_DIGITAL_TWIN_SERVICE_NAME,
+                INVEHICLE_DIGITAL_TWIN_SERVICE_VERSION,
+                INVEHICLE_DIGITAL_TWIN_SERVICE_COMMUNICATION_KIND,
+                INVEHICLE_DIGITAL_TWIN_SERVICE_COMMUNICATION_REFERENCE,
+            )
+        },
+    )
+    .await?;
 
-    // This could be enhanced to add retries for robustness
-    register_entity(&invehicle_digital_twin_uri, &provider_uri).await?;
+    // Discovery backends are not TLS-aware of the URIs they hand back, so upgrade them here,
+    // once, regardless of which backend found them.
+    let invehicle_digital_twin_uris: Vec<String> = invehicle_digital_twin_uris
+        .iter()
+        .map(|uri| tls.upgrade_uri(uri))
+        .collect();
+
+    debug!("Discovered In-Vehicle Digital Twin Service URIs {invehicle_digital_twin_uris:?}");
+
+    let load_balancer = LoadBalancer::new(settings.load_balancer_strategy, invehicle_digital_twin_uris)?;
+
+    // Every attempt asks the load balancer for an endpoint afresh, so after a failed attempt the
+    // retry helper's next attempt moves on to the next endpoint (`RoundRobin`) or a newly drawn
+    // random one, which can be the same endpoint again (`Random`).
+    retry_async(
+        settings.retry.max_attempts,
+        settings.retry.initial_delay(),
+        settings.retry.max_delay(),
+        || register_entity(load_balancer.next(), &provider_uri, &tls),
+    )
+    .await?;
     server_future.await?;
 
     signal::ctrl_c()
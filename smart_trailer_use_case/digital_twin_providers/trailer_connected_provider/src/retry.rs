@@ -0,0 +1,149 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT license.
+// SPDX-License-Identifier: MIT
+
+use rand::Rng;
+use std::future::Future;
+use std::time::Duration;
+use tonic::{Code, Status};
+
+/// Retry an async operation with exponential backoff and full jitter.
+///
+/// The delay after failed attempt `n` (zero-based), before the next attempt, is
+/// `min(max_delay, initial_delay * 2^n)` scaled by a random factor in `[0.5, 1.0]`. Only transient statuses (`Unavailable`, `DeadlineExceeded`)
+/// are retried; any other status is returned immediately.
+///
+/// # Arguments
+/// * `max_attempts` - The maximum number of attempts to make before giving up.
+/// * `initial_delay` - The base delay used to compute the first retry's backoff.
+/// * `max_delay` - The upper bound on the backoff delay.
+/// * `f` - A factory that produces a fresh future for each attempt.
+pub async fn retry_async<F, Fut, T>(
+    max_attempts: u32,
+    initial_delay: Duration,
+    max_delay: Duration,
+    mut f: F,
+) -> Result<T, Status>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, Status>>,
+{
+    let mut last_error = Status::internal("retry_async was called with max_attempts == 0");
+
+    for attempt in 0..max_attempts.max(1) {
+        match f().await {
+            Ok(value) => return Ok(value),
+            Err(status) => {
+                if !is_transient(&status) {
+                    return Err(status);
+                }
+                last_error = status;
+            }
+        }
+
+        if attempt + 1 == max_attempts {
+            break;
+        }
+
+        tokio::time::sleep(backoff_delay(attempt, initial_delay, max_delay)).await;
+    }
+
+    Err(last_error)
+}
+
+/// Returns true for statuses that represent a transient failure worth retrying, such as the
+/// callee or a connection to it being temporarily unavailable.
+fn is_transient(status: &Status) -> bool {
+    matches!(status.code(), Code::Unavailable | Code::DeadlineExceeded)
+}
+
+/// Compute the exponential-backoff-with-full-jitter delay for a given (zero-based) attempt.
+fn backoff_delay(attempt: u32, initial_delay: Duration, max_delay: Duration) -> Duration {
+    let exponential = 2u32
+        .checked_pow(attempt)
+        .and_then(|factor| initial_delay.checked_mul(factor))
+        .unwrap_or(max_delay);
+    let capped = exponential.min(max_delay);
+    let jitter_factor = rand::thread_rng().gen_range(0.5..=1.0);
+
+    capped.mul_f64(jitter_factor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[test]
+    fn backoff_delay_grows_exponentially_within_jitter_bounds() {
+        let initial_delay = Duration::from_millis(100);
+        let max_delay = Duration::from_secs(10);
+        let expected_unjittered = initial_delay * 4; // 100ms * 2^2
+
+        let delay = backoff_delay(2, initial_delay, max_delay);
+
+        assert!(delay <= expected_unjittered);
+        assert!(delay >= expected_unjittered.mul_f64(0.5));
+    }
+
+    #[test]
+    fn backoff_delay_caps_at_max_delay_even_on_overflow() {
+        let max_delay = Duration::from_secs(10);
+
+        // 2^32 overflows u32, which must fall back to max_delay rather than panicking.
+        let delay = backoff_delay(32, Duration::from_millis(500), max_delay);
+
+        assert!(delay <= max_delay);
+        assert!(delay >= max_delay.mul_f64(0.5));
+    }
+
+    #[tokio::test]
+    async fn retry_async_retries_transient_errors_then_succeeds() {
+        let attempts = AtomicU32::new(0);
+
+        let result = retry_async(5, Duration::from_millis(1), Duration::from_millis(2), || {
+            let attempt = attempts.fetch_add(1, Ordering::SeqCst);
+            async move {
+                if attempt < 2 {
+                    Err(Status::unavailable("not up yet"))
+                } else {
+                    Ok(42)
+                }
+            }
+        })
+        .await;
+
+        assert_eq!(result.unwrap(), 42);
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn retry_async_does_not_retry_non_transient_errors() {
+        let attempts = AtomicU32::new(0);
+
+        let result: Result<(), Status> =
+            retry_async(5, Duration::from_millis(1), Duration::from_millis(2), || {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                async { Err(Status::not_found("missing")) }
+            })
+            .await;
+
+        assert_eq!(result.unwrap_err().code(), Code::NotFound);
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn retry_async_gives_up_after_max_attempts() {
+        let attempts = AtomicU32::new(0);
+
+        let result: Result<(), Status> =
+            retry_async(3, Duration::from_millis(1), Duration::from_millis(2), || {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                async { Err(Status::unavailable("still down")) }
+            })
+            .await;
+
+        assert_eq!(result.unwrap_err().code(), Code::Unavailable);
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+}
@@ -0,0 +1,75 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT license.
+// SPDX-License-Identifier: MIT
+
+use async_trait::async_trait;
+use interfaces::chariott::service_discovery::core::v1::service_registry_client::ServiceRegistryClient;
+use interfaces::chariott::service_discovery::core::v1::DiscoverRequest;
+use tonic::{Request, Status};
+
+use super::ServiceDiscovery;
+use crate::tls::TlsConfig;
+
+/// A `ServiceDiscovery` backend that resolves services using Chariott's Service Discovery.
+pub struct ChariottServiceDiscovery {
+    /// Chariott's URI.
+    chariott_uri: String,
+    /// TLS configuration used to connect to Chariott.
+    tls: TlsConfig,
+}
+
+impl ChariottServiceDiscovery {
+    /// Create a new `ChariottServiceDiscovery`.
+    ///
+    /// # Arguments
+    /// * `chariott_uri` - Chariott's URI.
+    /// * `tls` - TLS configuration used to connect to Chariott.
+    pub fn new(chariott_uri: impl Into<String>, tls: TlsConfig) -> Self {
+        Self {
+            chariott_uri: chariott_uri.into(),
+            tls,
+        }
+    }
+}
+
+#[async_trait]
+impl ServiceDiscovery for ChariottServiceDiscovery {
+    async fn discover(
+        &self,
+        namespace: &str,
+        name: &str,
+        version: &str,
+        communication_kind: &str,
+        communication_reference: &str,
+    ) -> Result<Vec<String>, Status> {
+        let channel = self.tls.connect(&self.chariott_uri).await?;
+        let mut client = ServiceRegistryClient::new(channel);
+
+        let request = Request::new(DiscoverRequest {
+            namespace: namespace.to_string(),
+            name: name.to_string(),
+            version: version.to_string(),
+        });
+
+        let response = client
+            .discover(request)
+            .await
+            .map_err(|error| Status::internal(error.to_string()))?;
+
+        let service = response.into_inner().service.ok_or_else(|| {
+            Status::not_found(format!(
+                "Did not find a service in Chariott with namespace '{namespace}', name '{name}' and version {version}",
+            ))
+        })?;
+
+        if service.communication_kind != communication_kind
+            || service.communication_reference != communication_reference
+        {
+            return Err(Status::not_found(format!(
+                "Did not find a service in Chariott with namespace '{namespace}', name '{name}' and version {version} that has communication kind '{communication_kind}' and communication_reference '{communication_reference}'",
+            )));
+        }
+
+        Ok(vec![service.uri])
+    }
+}
@@ -0,0 +1,236 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT license.
+// SPDX-License-Identifier: MIT
+
+use async_trait::async_trait;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tonic::Status;
+
+use super::ServiceDiscovery;
+
+/// How long Consul waits for a TTL check heartbeat before marking the service critical.
+const CHECK_TTL: Duration = Duration::from_secs(15);
+/// How often this process re-confirms its own TTL check, once registered. Kept well under
+/// `CHECK_TTL` so a single missed heartbeat does not flip the service to critical.
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(5);
+/// How long Consul keeps a service registered after its check has been critical, before
+/// deregistering it entirely.
+const DEREGISTER_CRITICAL_SERVICE_AFTER: &str = "1m";
+
+/// A `ServiceDiscovery` backend that resolves and registers services against a Consul agent's
+/// HTTP catalog and health API.
+pub struct ConsulServiceDiscovery {
+    /// The Consul agent's HTTP API URI, e.g. `http://localhost:8500`.
+    agent_uri: String,
+    client: reqwest::Client,
+}
+
+impl ConsulServiceDiscovery {
+    /// Create a new `ConsulServiceDiscovery`.
+    ///
+    /// # Arguments
+    /// * `agent_uri` - The Consul agent's HTTP API URI.
+    pub fn new(agent_uri: impl Into<String>) -> Self {
+        Self {
+            agent_uri: agent_uri.into(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Mark `check_id` as passing.
+    async fn pass_check(&self, check_id: &str) -> Result<(), Status> {
+        let url = format!("{}/v1/agent/check/pass/{check_id}", self.agent_uri);
+        self.client
+            .put(&url)
+            .send()
+            .await
+            .map_err(map_reqwest_error)?;
+
+        Ok(())
+    }
+}
+
+/// Map a `reqwest::Error` to a `Status`, surfacing connection and timeout failures (e.g. the
+/// Consul agent not being up yet) as `Status::unavailable` so callers such as `retry_async` can
+/// tell them apart from a genuine application-level failure.
+fn map_reqwest_error(error: reqwest::Error) -> Status {
+    if error.is_connect() || error.is_timeout() {
+        Status::unavailable(error.to_string())
+    } else {
+        Status::internal(error.to_string())
+    }
+}
+
+/// A single entry returned by Consul's `/v1/health/service/<name>` endpoint.
+#[derive(Debug, Deserialize)]
+struct HealthServiceEntry {
+    #[serde(rename = "Node")]
+    node: ConsulNode,
+    #[serde(rename = "Service")]
+    service: ConsulService,
+}
+
+#[derive(Debug, Deserialize)]
+struct ConsulNode {
+    #[serde(rename = "Address")]
+    address: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ConsulService {
+    #[serde(rename = "Address")]
+    address: String,
+    #[serde(rename = "Port")]
+    port: u16,
+}
+
+/// The body sent to Consul's `/v1/agent/service/register` endpoint.
+#[derive(Debug, Serialize)]
+struct RegisterServiceRequest {
+    #[serde(rename = "ID")]
+    id: String,
+    #[serde(rename = "Name")]
+    name: String,
+    #[serde(rename = "Address")]
+    address: String,
+    #[serde(rename = "Port")]
+    port: u16,
+    #[serde(rename = "Check")]
+    check: RegisterServiceCheck,
+}
+
+/// A TTL health check attached to a service registration.
+///
+/// A TTL check (rather than an HTTP/GRPC check Consul would poll itself) is used because this
+/// provider only serves gRPC application traffic on `port`, with no separate health endpoint for
+/// Consul to probe; instead, this process heartbeats the check itself (see
+/// [`ConsulServiceDiscovery::pass_check`]).
+#[derive(Debug, Serialize)]
+struct RegisterServiceCheck {
+    #[serde(rename = "TTL")]
+    ttl: String,
+    #[serde(rename = "DeregisterCriticalServiceAfter")]
+    deregister_critical_service_after: String,
+}
+
+#[async_trait]
+impl ServiceDiscovery for ConsulServiceDiscovery {
+    async fn discover(
+        &self,
+        _namespace: &str,
+        name: &str,
+        _version: &str,
+        _communication_kind: &str,
+        _communication_reference: &str,
+    ) -> Result<Vec<String>, Status> {
+        let url = format!("{}/v1/health/service/{name}?passing=true", self.agent_uri);
+
+        let entries: Vec<HealthServiceEntry> = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .map_err(map_reqwest_error)?
+            .json()
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+
+        if entries.is_empty() {
+            return Err(Status::not_found(format!(
+                "Did not find a healthy '{name}' service in Consul"
+            )));
+        }
+
+        Ok(entries
+            .iter()
+            .map(|entry| {
+                let address = if !entry.service.address.is_empty() {
+                    &entry.service.address
+                } else {
+                    &entry.node.address
+                };
+
+                format!("http://{address}:{}", entry.service.port)
+            })
+            .collect())
+    }
+
+    async fn register(
+        &self,
+        _namespace: &str,
+        name: &str,
+        _version: &str,
+        _communication_kind: &str,
+        _communication_reference: &str,
+        uri: &str,
+    ) -> Result<(), Status> {
+        let parsed = uri
+            .parse::<http::Uri>()
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+        let address = parsed
+            .host()
+            .ok_or_else(|| Status::invalid_argument(format!("'{uri}' has no host")))?
+            .to_string();
+        let port = parsed
+            .port_u16()
+            .ok_or_else(|| Status::invalid_argument(format!("'{uri}' has no port")))?;
+
+        let service_id = format!("{name}-{address}-{port}");
+        let check_id = format!("service:{service_id}");
+
+        let request = RegisterServiceRequest {
+            id: service_id,
+            name: name.to_string(),
+            address,
+            port,
+            check: RegisterServiceCheck {
+                ttl: format!("{}s", CHECK_TTL.as_secs()),
+                deregister_critical_service_after: DEREGISTER_CRITICAL_SERVICE_AFTER.to_string(),
+            },
+        };
+
+        let url = format!("{}/v1/agent/service/register", self.agent_uri);
+        let response = self
+            .client
+            .put(&url)
+            .json(&request)
+            .send()
+            .await
+            .map_err(map_reqwest_error)?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let message = format!("Consul registration failed with status {status}");
+            // A 5xx here (e.g. the agent still electing a raft leader right after boot) is the
+            // same kind of transient, boot-ordering failure a connection refusal is, so it should
+            // be retried the same way.
+            return Err(if status.is_server_error() {
+                Status::unavailable(message)
+            } else {
+                Status::internal(message)
+            });
+        }
+
+        // Mark the check passing right away, then keep heartbeating it for as long as this
+        // process runs so Consul does not flip it to critical after `CHECK_TTL`.
+        self.pass_check(&check_id).await?;
+
+        let client = self.client.clone();
+        let agent_uri = self.agent_uri.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(HEARTBEAT_INTERVAL);
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                let url = format!("{agent_uri}/v1/agent/check/pass/{check_id}");
+                if let Err(e) = client.put(&url).send().await {
+                    warn!("Failed to heartbeat Consul TTL check '{check_id}': {e}");
+                }
+            }
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,60 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT license.
+// SPDX-License-Identifier: MIT
+
+use async_trait::async_trait;
+use tonic::Status;
+
+pub mod chariott;
+pub mod consul;
+pub mod zeroconf;
+
+/// Abstraction over a service-discovery backend.
+///
+/// This lets the provider locate the In-Vehicle Digital Twin service (and optionally register
+/// itself) without hard-coding a single discovery mechanism.
+#[async_trait]
+pub trait ServiceDiscovery: Send + Sync {
+    /// Discover a service, returning every matching candidate URI found.
+    ///
+    /// # Arguments
+    /// * `namespace` - The service's namespace.
+    /// * `name` - The service's name.
+    /// * `version` - The service's version.
+    /// * `communication_kind` - The service's communication kind.
+    /// * `communication_reference` - The service's communication reference.
+    async fn discover(
+        &self,
+        namespace: &str,
+        name: &str,
+        version: &str,
+        communication_kind: &str,
+        communication_reference: &str,
+    ) -> Result<Vec<String>, Status>;
+
+    /// Register a service with the backend.
+    ///
+    /// Backends that do not support registration (e.g. a read-only catalog) can rely on the
+    /// default implementation, which returns `Status::unimplemented`.
+    ///
+    /// # Arguments
+    /// * `namespace` - The service's namespace.
+    /// * `name` - The service's name.
+    /// * `version` - The service's version.
+    /// * `communication_kind` - The service's communication kind.
+    /// * `communication_reference` - The service's communication reference.
+    /// * `uri` - The service's URI.
+    async fn register(
+        &self,
+        _namespace: &str,
+        _name: &str,
+        _version: &str,
+        _communication_kind: &str,
+        _communication_reference: &str,
+        _uri: &str,
+    ) -> Result<(), Status> {
+        Err(Status::unimplemented(
+            "This service discovery backend does not support registration.",
+        ))
+    }
+}
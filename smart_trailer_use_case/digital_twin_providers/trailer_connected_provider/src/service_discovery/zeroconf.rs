@@ -0,0 +1,176 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT license.
+// SPDX-License-Identifier: MIT
+
+use async_trait::async_trait;
+use mdns_sd::{ServiceDaemon, ServiceEvent, ServiceInfo};
+use std::collections::HashSet;
+use std::net::IpAddr;
+use std::time::Duration;
+use tonic::Status;
+
+use super::ServiceDiscovery;
+
+/// The mDNS/zeroconf service type shared by every In-Vehicle Digital Twin provider and server.
+const SERVICE_TYPE: &str = "_invehicle-digital-twin._tcp.local.";
+
+/// Stop browsing once this many distinct matching responders have been found, rather than always
+/// waiting out the full browse timeout. A handful of candidates is enough for the load balancer
+/// to have somewhere to fail over to without needlessly holding up provider startup.
+const MAX_RESULTS: usize = 4;
+
+/// A `ServiceDiscovery` backend that finds the In-Vehicle Digital Twin on the local network
+/// segment via mDNS/zeroconf, without relying on a central registry such as Chariott.
+pub struct ZeroconfServiceDiscovery {
+    /// How long to browse for a matching responder before giving up.
+    browse_timeout: Duration,
+}
+
+impl ZeroconfServiceDiscovery {
+    /// Create a new `ZeroconfServiceDiscovery`.
+    ///
+    /// # Arguments
+    /// * `browse_timeout` - How long to browse for a matching responder before giving up.
+    pub fn new(browse_timeout: Duration) -> Self {
+        Self { browse_timeout }
+    }
+}
+
+#[async_trait]
+impl ServiceDiscovery for ZeroconfServiceDiscovery {
+    async fn discover(
+        &self,
+        namespace: &str,
+        name: &str,
+        version: &str,
+        _communication_kind: &str,
+        _communication_reference: &str,
+    ) -> Result<Vec<String>, Status> {
+        let daemon = ServiceDaemon::new().map_err(|e| Status::internal(e.to_string()))?;
+        let receiver = daemon
+            .browse(SERVICE_TYPE)
+            .map_err(|e| Status::internal(e.to_string()))?;
+
+        let deadline = tokio::time::Instant::now() + self.browse_timeout;
+        let mut seen_instances: HashSet<String> = HashSet::new();
+        let mut uris = Vec::new();
+
+        loop {
+            let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
+            if remaining.is_zero() {
+                break;
+            }
+
+            let event = match tokio::time::timeout(remaining, receiver.recv_async()).await {
+                Ok(Ok(event)) => event,
+                _ => break,
+            };
+
+            let ServiceEvent::ServiceResolved(info) = event else {
+                continue;
+            };
+
+            if !seen_instances.insert(info.get_fullname().to_string()) {
+                // Already considered this responder; a single instance can be resolved more
+                // than once on multi-homed hosts.
+                continue;
+            }
+
+            if !matches(&info, namespace, name, version) {
+                continue;
+            }
+
+            let Some(address) = info.get_addresses().iter().next() else {
+                continue;
+            };
+
+            uris.push(format!("http://{address}:{}", info.get_port()));
+
+            if uris.len() >= MAX_RESULTS {
+                break;
+            }
+        }
+
+        if uris.is_empty() {
+            return Err(Status::not_found(format!(
+                "Did not find a zeroconf responder for namespace '{namespace}', name '{name}' and version '{version}' within the browse timeout"
+            )));
+        }
+
+        Ok(uris)
+    }
+}
+
+/// Check whether a resolved mDNS service's TXT records match the requested
+/// namespace/name/version.
+fn matches(info: &ServiceInfo, namespace: &str, name: &str, version: &str) -> bool {
+    let properties = info.get_properties();
+    properties.get_property_val_str("namespace") == Some(namespace)
+        && properties.get_property_val_str("name") == Some(name)
+        && properties.get_property_val_str("version") == Some(version)
+}
+
+/// Advertise this provider on the local network segment via mDNS/zeroconf so that the
+/// In-Vehicle Digital Twin (or any other consumer) can find it without Chariott running.
+///
+/// # Arguments
+/// * `provider_authority` - The host:port the provider's gRPC server is bound to.
+/// * `namespace` - The provider's namespace.
+/// * `name` - The provider's name.
+/// * `version` - The provider's version.
+/// * `communication_kind` - The provider's communication kind.
+/// * `entity_id` - The id of the entity this provider serves.
+pub fn advertise_provider(
+    provider_authority: &str,
+    namespace: &str,
+    name: &str,
+    version: &str,
+    communication_kind: &str,
+    entity_id: &str,
+) -> Result<ServiceDaemon, Status> {
+    let daemon = ServiceDaemon::new().map_err(|e| Status::internal(e.to_string()))?;
+
+    let (host, port) = provider_authority
+        .rsplit_once(':')
+        .ok_or_else(|| Status::invalid_argument(format!("'{provider_authority}' is not a host:port authority")))?;
+    let port: u16 = port
+        .parse()
+        .map_err(|_| Status::invalid_argument(format!("'{provider_authority}' has an invalid port")))?;
+    let hostname = format!("{name}.local.");
+
+    let properties = [
+        ("namespace", namespace),
+        ("name", name),
+        ("version", version),
+        ("communication_kind", communication_kind),
+        ("entity_id", entity_id),
+    ];
+
+    // A wildcard bind address (e.g. the default `0.0.0.0:55000`) is not something a remote
+    // consumer can connect to, so let mdns-sd auto-detect and advertise the host's real
+    // interface addresses instead of the literal bind address in that case.
+    let is_wildcard = host
+        .parse::<IpAddr>()
+        .map(|ip| ip.is_unspecified())
+        .unwrap_or(false);
+
+    let mut service_info = ServiceInfo::new(
+        SERVICE_TYPE,
+        name,
+        &hostname,
+        if is_wildcard { "" } else { host },
+        port,
+        &properties[..],
+    )
+    .map_err(|e| Status::internal(e.to_string()))?;
+
+    if is_wildcard {
+        service_info = service_info.enable_addr_auto();
+    }
+
+    daemon
+        .register(service_info)
+        .map_err(|e| Status::internal(e.to_string()))?;
+
+    Ok(daemon)
+}
@@ -0,0 +1,362 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT license.
+// SPDX-License-Identifier: MIT
+
+use clap::Parser;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::load_balancer::LoadBalancerStrategy;
+use crate::tls::TlsConfig;
+
+/// The discovery backend the provider should use to find the In-Vehicle Digital Twin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiscoveryBackendKind {
+    #[default]
+    Chariott,
+    Consul,
+    Zeroconf,
+}
+
+impl FromStr for DiscoveryBackendKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "chariott" => Ok(Self::Chariott),
+            "consul" => Ok(Self::Consul),
+            "zeroconf" => Ok(Self::Zeroconf),
+            other => Err(format!("'{other}' is not a known discovery backend")),
+        }
+    }
+}
+
+/// TLS-related settings, deserialized from the `[tls]` table.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct TlsSettings {
+    pub enabled: bool,
+    pub cert_path: Option<PathBuf>,
+    pub key_path: Option<PathBuf>,
+    pub ca_path: Option<PathBuf>,
+    pub mutual_tls: bool,
+    pub domain_name: Option<String>,
+}
+
+impl From<TlsSettings> for TlsConfig {
+    fn from(settings: TlsSettings) -> Self {
+        Self {
+            enabled: settings.enabled,
+            cert_path: settings.cert_path,
+            key_path: settings.key_path,
+            ca_path: settings.ca_path,
+            mutual_tls: settings.mutual_tls,
+            domain_name: settings.domain_name,
+        }
+    }
+}
+
+/// Retry/backoff settings, deserialized from the `[retry]` table.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(default)]
+pub struct RetrySettings {
+    pub max_attempts: u32,
+    pub initial_delay_ms: u64,
+    pub max_delay_ms: u64,
+}
+
+impl Default for RetrySettings {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            initial_delay_ms: 500,
+            max_delay_ms: 10_000,
+        }
+    }
+}
+
+impl RetrySettings {
+    pub fn initial_delay(&self) -> Duration {
+        Duration::from_millis(self.initial_delay_ms)
+    }
+
+    pub fn max_delay(&self) -> Duration {
+        Duration::from_millis(self.max_delay_ms)
+    }
+}
+
+/// Runtime settings for the trailer-connected provider.
+///
+/// Settings are layered, in increasing precedence: built-in defaults, `provider.toml`,
+/// `PROVIDER_*` environment variables, then command-line flags.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub discovery_backend: DiscoveryBackendKind,
+    pub discovery_uri: String,
+    pub provider_authority: String,
+    pub advertised_uri: Option<String>,
+    pub log_level: String,
+    pub load_balancer_strategy: LoadBalancerStrategy,
+    pub tls: TlsSettings,
+    pub retry: RetrySettings,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            discovery_backend: DiscoveryBackendKind::default(),
+            discovery_uri: "http://0.0.0.0:50000".to_string(),
+            provider_authority: "0.0.0.0:55000".to_string(),
+            advertised_uri: None,
+            log_level: "debug".to_string(),
+            load_balancer_strategy: LoadBalancerStrategy::default(),
+            tls: TlsSettings::default(),
+            retry: RetrySettings::default(),
+        }
+    }
+}
+
+/// Command-line flags. Any flag left unset falls back to the environment, then the
+/// configuration file, then the built-in default.
+#[derive(Debug, Parser, Default)]
+pub struct SettingsArgs {
+    /// Path to the TOML configuration file.
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+    #[arg(long)]
+    pub discovery_backend: Option<String>,
+    #[arg(long)]
+    pub discovery_uri: Option<String>,
+    #[arg(long)]
+    pub provider_authority: Option<String>,
+    #[arg(long)]
+    pub advertised_uri: Option<String>,
+    #[arg(long)]
+    pub log_level: Option<String>,
+    #[arg(long)]
+    pub load_balancer_strategy: Option<String>,
+    #[arg(long)]
+    pub tls_enabled: Option<bool>,
+    #[arg(long)]
+    pub tls_cert_path: Option<PathBuf>,
+    #[arg(long)]
+    pub tls_key_path: Option<PathBuf>,
+    #[arg(long)]
+    pub tls_ca_path: Option<PathBuf>,
+    #[arg(long)]
+    pub tls_mutual_tls: Option<bool>,
+    #[arg(long)]
+    pub tls_domain_name: Option<String>,
+    #[arg(long)]
+    pub retry_max_attempts: Option<u32>,
+    #[arg(long)]
+    pub retry_initial_delay_ms: Option<u64>,
+    #[arg(long)]
+    pub retry_max_delay_ms: Option<u64>,
+}
+
+const DEFAULT_CONFIG_PATH: &str = "provider.toml";
+
+impl Settings {
+    /// Load settings from the config file (`provider.toml`, or the path given by `--config`),
+    /// the environment and `std::env::args`, applied in that order of increasing precedence.
+    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        Self::load_from(SettingsArgs::parse())
+    }
+
+    /// Load settings, with command-line flags already parsed into `args`. Exposed separately
+    /// from [`Settings::load`] so tests and embedders can supply `args` without touching
+    /// `std::env::args`.
+    pub fn load_from(args: SettingsArgs) -> Result<Self, Box<dyn std::error::Error>> {
+        let config_path = args
+            .config
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_CONFIG_PATH));
+
+        let mut settings = Self::from_file(&config_path)?;
+        settings.apply_env();
+        settings.apply_args(&args);
+
+        Ok(settings)
+    }
+
+    /// Read `path` as a `Settings` TOML file, falling back to built-in defaults if it does not
+    /// exist.
+    fn from_file(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let contents = std::fs::read_to_string(path)?;
+        Ok(toml::from_str(&contents)?)
+    }
+
+    fn apply_env(&mut self) {
+        if let Ok(value) = std::env::var("PROVIDER_DISCOVERY_BACKEND") {
+            if let Ok(backend) = value.parse() {
+                self.discovery_backend = backend;
+            }
+        }
+        if let Ok(value) = std::env::var("PROVIDER_DISCOVERY_URI") {
+            self.discovery_uri = value;
+        }
+        if let Ok(value) = std::env::var("PROVIDER_AUTHORITY") {
+            self.provider_authority = value;
+        }
+        if let Ok(value) = std::env::var("PROVIDER_ADVERTISED_URI") {
+            self.advertised_uri = Some(value);
+        }
+        if let Ok(value) = std::env::var("PROVIDER_LOG_LEVEL") {
+            self.log_level = value;
+        }
+        if let Ok(value) = std::env::var("PROVIDER_LOAD_BALANCER_STRATEGY") {
+            if let Ok(strategy) = value.parse() {
+                self.load_balancer_strategy = strategy;
+            }
+        }
+        if let Ok(value) = std::env::var("PROVIDER_TLS_ENABLED") {
+            if let Ok(enabled) = value.parse() {
+                self.tls.enabled = enabled;
+            }
+        }
+        if let Ok(value) = std::env::var("PROVIDER_TLS_CERT_PATH") {
+            self.tls.cert_path = Some(PathBuf::from(value));
+        }
+        if let Ok(value) = std::env::var("PROVIDER_TLS_KEY_PATH") {
+            self.tls.key_path = Some(PathBuf::from(value));
+        }
+        if let Ok(value) = std::env::var("PROVIDER_TLS_CA_PATH") {
+            self.tls.ca_path = Some(PathBuf::from(value));
+        }
+        if let Ok(value) = std::env::var("PROVIDER_TLS_MUTUAL_TLS") {
+            if let Ok(mutual_tls) = value.parse() {
+                self.tls.mutual_tls = mutual_tls;
+            }
+        }
+        if let Ok(value) = std::env::var("PROVIDER_TLS_DOMAIN_NAME") {
+            self.tls.domain_name = Some(value);
+        }
+        if let Ok(value) = std::env::var("PROVIDER_RETRY_MAX_ATTEMPTS") {
+            if let Ok(max_attempts) = value.parse() {
+                self.retry.max_attempts = max_attempts;
+            }
+        }
+        if let Ok(value) = std::env::var("PROVIDER_RETRY_INITIAL_DELAY_MS") {
+            if let Ok(initial_delay_ms) = value.parse() {
+                self.retry.initial_delay_ms = initial_delay_ms;
+            }
+        }
+        if let Ok(value) = std::env::var("PROVIDER_RETRY_MAX_DELAY_MS") {
+            if let Ok(max_delay_ms) = value.parse() {
+                self.retry.max_delay_ms = max_delay_ms;
+            }
+        }
+    }
+
+    fn apply_args(&mut self, args: &SettingsArgs) {
+        if let Some(value) = &args.discovery_backend {
+            if let Ok(backend) = value.parse() {
+                self.discovery_backend = backend;
+            }
+        }
+        if let Some(value) = &args.discovery_uri {
+            self.discovery_uri = value.clone();
+        }
+        if let Some(value) = &args.provider_authority {
+            self.provider_authority = value.clone();
+        }
+        if let Some(value) = &args.advertised_uri {
+            self.advertised_uri = Some(value.clone());
+        }
+        if let Some(value) = &args.log_level {
+            self.log_level = value.clone();
+        }
+        if let Some(value) = &args.load_balancer_strategy {
+            if let Ok(strategy) = value.parse() {
+                self.load_balancer_strategy = strategy;
+            }
+        }
+        if let Some(value) = args.tls_enabled {
+            self.tls.enabled = value;
+        }
+        if let Some(value) = &args.tls_cert_path {
+            self.tls.cert_path = Some(value.clone());
+        }
+        if let Some(value) = &args.tls_key_path {
+            self.tls.key_path = Some(value.clone());
+        }
+        if let Some(value) = &args.tls_ca_path {
+            self.tls.ca_path = Some(value.clone());
+        }
+        if let Some(value) = args.tls_mutual_tls {
+            self.tls.mutual_tls = value;
+        }
+        if let Some(value) = &args.tls_domain_name {
+            self.tls.domain_name = Some(value.clone());
+        }
+        if let Some(value) = args.retry_max_attempts {
+            self.retry.max_attempts = value;
+        }
+        if let Some(value) = args.retry_initial_delay_ms {
+            self.retry.initial_delay_ms = value;
+        }
+        if let Some(value) = args.retry_max_delay_ms {
+            self.retry.max_delay_ms = value;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    // `apply_env` reads real process environment variables, which are global state shared by
+    // every test in this binary; serialize the tests that touch `PROVIDER_DISCOVERY_URI` so they
+    // can't interleave and observe each other's values.
+    static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+    #[test]
+    fn flag_overrides_env_which_overrides_default() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        std::env::remove_var("PROVIDER_DISCOVERY_URI");
+
+        let mut settings = Settings::default();
+        assert_eq!(settings.discovery_uri, "http://0.0.0.0:50000");
+
+        std::env::set_var("PROVIDER_DISCOVERY_URI", "http://from-env:1234");
+        settings.apply_env();
+        assert_eq!(settings.discovery_uri, "http://from-env:1234");
+
+        let args = SettingsArgs {
+            discovery_uri: Some("http://from-flag:9999".to_string()),
+            ..Default::default()
+        };
+        settings.apply_args(&args);
+        assert_eq!(settings.discovery_uri, "http://from-flag:9999");
+
+        std::env::remove_var("PROVIDER_DISCOVERY_URI");
+    }
+
+    #[test]
+    fn env_is_ignored_when_unset() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        std::env::remove_var("PROVIDER_DISCOVERY_URI");
+
+        let mut settings = Settings::default();
+        settings.apply_env();
+
+        assert_eq!(settings.discovery_uri, "http://0.0.0.0:50000");
+    }
+
+    #[test]
+    fn from_file_falls_back_to_defaults_when_the_config_file_is_missing() {
+        let settings = Settings::from_file(Path::new("/nonexistent/provider.toml")).unwrap();
+
+        assert_eq!(settings.discovery_uri, Settings::default().discovery_uri);
+    }
+}
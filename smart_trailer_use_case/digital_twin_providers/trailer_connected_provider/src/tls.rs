@@ -0,0 +1,109 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT license.
+// SPDX-License-Identifier: MIT
+
+use std::path::PathBuf;
+use tonic::transport::{Certificate, Channel, ClientTlsConfig, Endpoint, Identity, ServerTlsConfig};
+use tonic::Status;
+
+/// TLS configuration shared by the provider's gRPC server and its outbound clients.
+///
+/// When `enabled` is `false`, `connect` and `upgrade_uri` leave connections and URIs plaintext,
+/// so this struct can be threaded through unconditionally. `server_tls_config` and
+/// `client_tls_config` do not look at `enabled` and always try to build a TLS config.
+#[derive(Debug, Clone, Default)]
+pub struct TlsConfig {
+    /// Whether TLS should be used at all.
+    pub enabled: bool,
+    /// PEM-encoded certificate used to prove this process's identity (server cert, or client
+    /// cert when `mutual_tls` is set).
+    pub cert_path: Option<PathBuf>,
+    /// PEM-encoded private key matching `cert_path`.
+    pub key_path: Option<PathBuf>,
+    /// PEM-encoded CA root used to verify the peer's certificate.
+    pub ca_path: Option<PathBuf>,
+    /// Whether the server should require and verify a client certificate, and whether outbound
+    /// clients should present one.
+    pub mutual_tls: bool,
+    /// Overrides the domain name used to verify the server's certificate, for outbound clients.
+    pub domain_name: Option<String>,
+}
+
+impl TlsConfig {
+    /// Build a `ServerTlsConfig` for `tonic::transport::Server`.
+    pub fn server_tls_config(&self) -> Result<ServerTlsConfig, Status> {
+        let cert = read_pem(self.cert_path.as_ref(), "cert_path")?;
+        let key = read_pem(self.key_path.as_ref(), "key_path")?;
+
+        let mut tls_config = ServerTlsConfig::new().identity(Identity::from_pem(cert, key));
+
+        if self.mutual_tls {
+            let ca = read_pem(self.ca_path.as_ref(), "ca_path")?;
+            tls_config = tls_config.client_ca_root(Certificate::from_pem(ca));
+        }
+
+        Ok(tls_config)
+    }
+
+    /// Build a `ClientTlsConfig` for an outbound `tonic::transport::Endpoint`.
+    pub fn client_tls_config(&self) -> Result<ClientTlsConfig, Status> {
+        let mut tls_config = ClientTlsConfig::new();
+
+        if let Some(ca_path) = &self.ca_path {
+            tls_config = tls_config.ca_certificate(Certificate::from_pem(read_pem(
+                Some(ca_path),
+                "ca_path",
+            )?));
+        }
+
+        if let Some(domain_name) = &self.domain_name {
+            tls_config = tls_config.domain_name(domain_name);
+        }
+
+        if self.mutual_tls {
+            let cert = read_pem(self.cert_path.as_ref(), "cert_path")?;
+            let key = read_pem(self.key_path.as_ref(), "key_path")?;
+            tls_config = tls_config.identity(Identity::from_pem(cert, key));
+        }
+
+        Ok(tls_config)
+    }
+
+    /// Upgrade a discovered `http://` URI to `https://` when TLS is enabled.
+    pub fn upgrade_uri(&self, uri: &str) -> String {
+        if self.enabled {
+            uri.replacen("http://", "https://", 1)
+        } else {
+            uri.to_string()
+        }
+    }
+
+    /// Connect to `uri`, configuring the outbound channel for TLS when enabled.
+    ///
+    /// # Arguments
+    /// * `uri` - The URI to connect to.
+    pub async fn connect(&self, uri: &str) -> Result<Channel, Status> {
+        let mut endpoint =
+            Endpoint::from_shared(uri.to_string()).map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+        if self.enabled {
+            endpoint = endpoint
+                .tls_config(self.client_tls_config()?)
+                .map_err(|e| Status::internal(e.to_string()))?;
+        }
+
+        endpoint
+            .connect()
+            .await
+            .map_err(|e| Status::unavailable(e.to_string()))
+    }
+}
+
+/// Read a PEM file, producing a `Status` naming the missing/unreadable field on failure.
+fn read_pem(path: Option<&PathBuf>, field_name: &str) -> Result<Vec<u8>, Status> {
+    let path = path.ok_or_else(|| {
+        Status::invalid_argument(format!("TLS is enabled but '{field_name}' was not configured"))
+    })?;
+
+    std::fs::read(path).map_err(|e| Status::internal(format!("Failed to read '{}': {e}", path.display())))
+}